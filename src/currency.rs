@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::split::allocate_by_weight;
+use crate::{CurrencyUnit, SimplifyError, Transaction};
+
+/// A currency tag, e.g. `Currency::new("USD")`. Transactions and debts
+/// carry one of these so amounts from different currencies are never
+/// mixed without an explicit conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency(&'static str);
+
+impl Currency {
+    pub const fn new(code: &'static str) -> Self {
+        Currency(code)
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// Converts every transaction into `base`'s minor units using `rates`
+/// (each entry being how many units of `base` one unit of the key currency
+/// is worth), returning transactions that are all in `base`.
+///
+/// A transaction already in `base` passes through unchanged. Otherwise,
+/// the transaction's total is converted and then redistributed across its
+/// participants using the same largest-remainder allocation
+/// `SplitStrategy` uses, so the converted per-person shares still sum
+/// exactly to the converted total and keep the same relative split as the
+/// original.
+pub fn convert_to_base(
+    transactions: Vec<Transaction>,
+    base: Currency,
+    rates: &HashMap<Currency, Decimal>,
+) -> Result<Vec<Transaction>, SimplifyError> {
+    transactions
+        .into_iter()
+        .map(|transaction| convert_transaction(transaction, base, rates))
+        .collect()
+}
+
+fn convert_transaction(
+    transaction: Transaction,
+    base: Currency,
+    rates: &HashMap<Currency, Decimal>,
+) -> Result<Transaction, SimplifyError> {
+    if transaction.currency == base {
+        return Ok(transaction);
+    }
+
+    let rate = *rates.get(&transaction.currency).ok_or(SimplifyError::MissingRate {
+        currency: transaction.currency,
+    })?;
+
+    let mut original_total: CurrencyUnit = 0;
+    for (_, amount) in transaction.split_by.iter() {
+        original_total = original_total
+            .checked_add(*amount)
+            .ok_or(SimplifyError::Overflow)?;
+    }
+    if let Some(expected) = transaction.declared_total {
+        if expected != original_total {
+            return Err(SimplifyError::UnbalancedTransaction {
+                expected,
+                actual: original_total,
+            });
+        }
+    }
+
+    let converted_total = (Decimal::from(original_total) * rate)
+        .round()
+        .to_i64()
+        .ok_or(SimplifyError::Overflow)?;
+
+    let weights = transaction
+        .split_by
+        .into_iter()
+        .map(|(person, amount)| (person, Decimal::from(amount)))
+        .collect();
+
+    Ok(Transaction {
+        paid_by: transaction.paid_by,
+        split_by: allocate_by_weight(converted_total, weights)?,
+        declared_total: Some(converted_total),
+        currency: base,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Person;
+
+    #[test]
+    fn test_convert_to_base_preserves_relative_split_and_total() {
+        const USD: Currency = Currency::new("USD");
+        const EUR: Currency = Currency::new("EUR");
+
+        let mut rates = HashMap::new();
+        rates.insert(EUR, Decimal::new(11, 1)); // 1 EUR = 1.1 USD
+
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("A"), 50), (Person::new("B"), 50)],
+            declared_total: None,
+            currency: EUR,
+        }];
+
+        let converted = convert_to_base(transactions, USD, &rates).unwrap();
+        assert_eq!(converted.len(), 1);
+        assert_eq!(converted[0].currency, USD);
+
+        let total: CurrencyUnit = converted[0].split_by.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(total, 110);
+    }
+
+    #[test]
+    fn test_convert_to_base_rejects_missing_rate() {
+        const USD: Currency = Currency::new("USD");
+        const EUR: Currency = Currency::new("EUR");
+
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("B"), 50)],
+            declared_total: None,
+            currency: EUR,
+        }];
+
+        let err = convert_to_base(transactions, USD, &HashMap::new()).unwrap_err();
+        assert_eq!(err, SimplifyError::MissingRate { currency: EUR });
+    }
+
+    #[test]
+    fn test_convert_to_base_validates_declared_total() {
+        const USD: Currency = Currency::new("USD");
+        const EUR: Currency = Currency::new("EUR");
+
+        let mut rates = HashMap::new();
+        rates.insert(EUR, Decimal::new(11, 1)); // 1 EUR = 1.1 USD
+
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("A"), 50), (Person::new("B"), 50)],
+            declared_total: Some(1000),
+            currency: EUR,
+        }];
+
+        let err = convert_to_base(transactions, USD, &rates).unwrap_err();
+        assert_eq!(
+            err,
+            SimplifyError::UnbalancedTransaction {
+                expected: 1000,
+                actual: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_to_base_rejects_overflowing_split_total() {
+        const USD: Currency = Currency::new("USD");
+        const EUR: Currency = Currency::new("EUR");
+
+        let mut rates = HashMap::new();
+        rates.insert(EUR, Decimal::new(11, 1)); // 1 EUR = 1.1 USD
+
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("A"), CurrencyUnit::MAX), (Person::new("B"), 10)],
+            declared_total: None,
+            currency: EUR,
+        }];
+
+        let err = convert_to_base(transactions, USD, &rates).unwrap_err();
+        assert_eq!(err, SimplifyError::Overflow);
+    }
+
+    #[test]
+    fn test_convert_to_base_carries_forward_converted_declared_total() {
+        const USD: Currency = Currency::new("USD");
+        const EUR: Currency = Currency::new("EUR");
+
+        let mut rates = HashMap::new();
+        rates.insert(EUR, Decimal::new(11, 1)); // 1 EUR = 1.1 USD
+
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("A"), 50), (Person::new("B"), 50)],
+            declared_total: Some(100),
+            currency: EUR,
+        }];
+
+        let converted = convert_to_base(transactions, USD, &rates).unwrap();
+        assert_eq!(converted[0].declared_total, Some(110));
+    }
+}
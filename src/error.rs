@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::{Currency, CurrencyUnit, Person};
+
+/// Failure modes `simplify` and `simplify_minimal` can report when a
+/// ledger of transactions doesn't describe a coherent set of balances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimplifyError {
+    /// A transaction's `split_by` had no entries, so it has no way to
+    /// attribute its cost.
+    EmptySplit,
+    /// A `split_by` entry had a negative amount.
+    NegativeAmount { person: Person },
+    /// Accumulating balances overflowed `CurrencyUnit`.
+    Overflow,
+    /// A transaction declared a total that its `split_by` entries don't
+    /// sum to.
+    UnbalancedTransaction {
+        expected: CurrencyUnit,
+        actual: CurrencyUnit,
+    },
+    /// The transactions being settled together aren't all in the same
+    /// currency; convert them to a common base currency first.
+    MixedCurrencies,
+    /// `convert_to_base` had no exchange rate for this currency.
+    MissingRate { currency: Currency },
+    /// A `SplitStrategy` allocated by weight (`Equal`, `Shares`,
+    /// `Percentage`) had a total weight of zero, so there's no way to
+    /// proportionally distribute the transaction's total.
+    ZeroWeightTotal,
+}
+
+impl fmt::Display for SimplifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimplifyError::EmptySplit => write!(f, "transaction has an empty split"),
+            SimplifyError::NegativeAmount { person } => {
+                write!(f, "negative split amount for {person:?}")
+            }
+            SimplifyError::Overflow => write!(f, "balance calculation overflowed"),
+            SimplifyError::UnbalancedTransaction { expected, actual } => write!(
+                f,
+                "transaction total {expected} does not match split total {actual}"
+            ),
+            SimplifyError::MixedCurrencies => {
+                write!(f, "transactions must share one currency before simplifying")
+            }
+            SimplifyError::MissingRate { currency } => {
+                write!(f, "no exchange rate for currency {}", currency.code())
+            }
+            SimplifyError::ZeroWeightTotal => {
+                write!(f, "split strategy has a total weight of zero")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimplifyError {}
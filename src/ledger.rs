@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{simplify, Currency, CurrencyUnit, Debt, Person, SimplifyError, Transaction};
+
+/// The ledger CSV schema has no currency column, so every row it
+/// produces is assumed to be in this currency.
+const LEDGER_CURRENCY: Currency = Currency::new("USD");
+
+/// A single row of an expense ledger CSV, before rows sharing an
+/// `expense_id` are grouped into a `Transaction`.
+#[derive(Debug, Deserialize)]
+struct ExpenseRow {
+    expense_id: String,
+    payer: String,
+    participant: String,
+    amount: CurrencyUnit,
+}
+
+/// A single row of a settled-debt CSV.
+#[derive(Debug, Serialize)]
+struct DebtRow<'a> {
+    owing: &'a str,
+    owed: &'a str,
+    amount: CurrencyUnit,
+}
+
+/// Failure modes when reading a ledger or writing settled debts.
+#[derive(Debug)]
+pub enum LedgerError {
+    Csv(csv::Error),
+    Simplify(SimplifyError),
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Csv(err) => write!(f, "csv error: {err}"),
+            LedgerError::Simplify(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl From<csv::Error> for LedgerError {
+    fn from(err: csv::Error) -> Self {
+        LedgerError::Csv(err)
+    }
+}
+
+impl From<SimplifyError> for LedgerError {
+    fn from(err: SimplifyError) -> Self {
+        LedgerError::Simplify(err)
+    }
+}
+
+/// Reads an expense ledger CSV with columns `expense_id,payer,participant,amount`
+/// (rows sharing an `expense_id` are grouped into one `Transaction`), then
+/// simplifies it into a minimal set of settlements.
+pub fn simplify_from_reader<R: Read>(r: R) -> Result<Vec<Debt>, LedgerError> {
+    let transactions = read_transactions(r)?;
+    Ok(simplify(transactions)?)
+}
+
+fn read_transactions<R: Read>(r: R) -> Result<Vec<Transaction>, LedgerError> {
+    let mut reader = csv::Reader::from_reader(r);
+
+    // Rows are grouped by `expense_id`, preserving the order expense ids
+    // first appear in so the resulting transactions are deterministic.
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: HashMap<String, (Person, Vec<(Person, CurrencyUnit)>)> = HashMap::new();
+
+    for result in reader.deserialize() {
+        let row: ExpenseRow = result?;
+        let entry = grouped.entry(row.expense_id.clone()).or_insert_with(|| {
+            order.push(row.expense_id.clone());
+            (Person::new(&row.payer), Vec::new())
+        });
+        entry.1.push((Person::new(&row.participant), row.amount));
+    }
+
+    Ok(order
+        .into_iter()
+        .map(|expense_id| {
+            let (paid_by, split_by) = grouped.remove(&expense_id).unwrap();
+            Transaction {
+                paid_by,
+                split_by,
+                declared_total: None,
+                currency: LEDGER_CURRENCY,
+            }
+        })
+        .collect())
+}
+
+/// Writes settled debts as a CSV with columns `owing,owed,amount`.
+pub fn write_debts<W: Write>(debts: &[Debt], w: W) -> Result<(), LedgerError> {
+    let mut writer = csv::Writer::from_writer(w);
+
+    for debt in debts {
+        writer.serialize(DebtRow {
+            owing: debt.owing.as_str(),
+            owed: debt.owed.as_str(),
+            amount: debt.amount,
+        })?;
+    }
+
+    writer.flush().map_err(|err| LedgerError::Csv(err.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplify_from_reader_groups_rows_by_expense_id() {
+        let csv = "\
+expense_id,payer,participant,amount
+1,A,A,50
+1,A,B,50
+2,B,A,25
+2,B,B,25
+";
+
+        let debts = simplify_from_reader(csv.as_bytes()).unwrap();
+        assert_eq!(
+            debts,
+            vec![Debt {
+                amount: 25,
+                owing: Person::new("B"),
+                owed: Person::new("A"),
+                currency: LEDGER_CURRENCY,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_debts_round_trips_through_csv() {
+        let debts = vec![Debt {
+            amount: 25,
+            owing: Person::new("B"),
+            owed: Person::new("A"),
+            currency: LEDGER_CURRENCY,
+        }];
+
+        let mut buffer = Vec::new();
+        write_debts(&debts, &mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "owing,owed,amount\nB,A,25\n"
+        );
+    }
+}
@@ -1,13 +1,60 @@
-use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+mod currency;
+mod error;
+mod ledger;
+mod simplify;
+mod split;
+
+pub use currency::{convert_to_base, Currency};
+pub use error::SimplifyError;
+pub use ledger::{simplify_from_reader, write_debts, LedgerError};
+pub use simplify::{simplify, simplify_minimal};
+pub use split::SplitStrategy;
+
+/// A participant in a ledger, identified by name. Cheap to clone since the
+/// name is reference-counted, so the same `Person` can be shared across a
+/// ledger's transactions without re-allocating.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Person(Arc<str>);
+
+impl Person {
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Person(Arc::from(name.as_ref()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Person {
+    fn from(name: &str) -> Self {
+        Person::new(name)
+    }
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Person(&'static str);
+impl From<String> for Person {
+    fn from(name: String) -> Self {
+        Person(Arc::from(name.as_str()))
+    }
+}
 
 pub type CurrencyUnit = i64;
 
+#[derive(Debug)]
 pub struct Transaction {
     pub paid_by: Person,
     pub split_by: Vec<(Person, CurrencyUnit)>,
+    /// An optional declared total for this transaction. When present,
+    /// `simplify`/`simplify_minimal` reject the transaction unless
+    /// `split_by` sums to exactly this amount.
+    pub declared_total: Option<CurrencyUnit>,
+    /// The currency `split_by`'s amounts are denominated in. `simplify`
+    /// and `simplify_minimal` require every transaction they settle to
+    /// share one currency; use `convert_to_base` first to normalize a
+    /// mixed-currency ledger.
+    pub currency: Currency,
 }
 
 #[derive(Debug, PartialEq)]
@@ -15,120 +62,5 @@ pub struct Debt {
     pub amount: CurrencyUnit,
     pub owing: Person,
     pub owed: Person,
-}
-
-pub fn simplify(transactions: Vec<Transaction>) -> Vec<Debt> {
-    let mut balances: HashMap<Person, CurrencyUnit> = HashMap::new();
-
-    // Calculate the net balance for each person
-    for transaction in transactions.iter() {
-        let total = transaction
-            .split_by
-            .iter()
-            .map(|(_, amount)| amount)
-            .sum::<CurrencyUnit>();
-
-        // Credit the person who paid
-        *balances.entry(transaction.paid_by).or_default() += total;
-
-        // Debit the people who owe
-        for (person, amount) in transaction.split_by.iter() {
-            *balances.entry(*person).or_default() -= amount;
-        }
-    }
-
-    // Sort the balances by the amount owing
-    let mut sorted_balances: BTreeMap<CurrencyUnit, Person> = balances
-        .into_iter()
-        .filter_map(|(person, amount)| {
-            if amount != 0 {
-                Some((amount, person))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    let mut debts = vec![];
-
-    // Pop the smallest and largest balances and settle them, adding any remaining balance back to the sorted balances
-    while sorted_balances.len() > 1 {
-        let (min_amount, min_person) = sorted_balances.pop_first().unwrap();
-        let (max_amount, max_person) = sorted_balances.pop_last().unwrap();
-
-        let amount = min_amount.abs().min(max_amount.abs());
-        let debt = Debt {
-            amount,
-            owing: min_person,
-            owed: max_person,
-        };
-
-        if min_amount.abs() > max_amount.abs() {
-            sorted_balances.insert(min_amount + max_amount, min_person);
-        } else if max_amount.abs() > min_amount.abs() {
-            sorted_balances.insert(min_amount + max_amount, max_person);
-        }
-
-        debts.push(debt);
-    }
-
-    debts
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_simplify() {
-        let transactions = vec![
-            Transaction {
-                paid_by: Person("A"),
-                split_by: vec![(Person("A"), 50), (Person("B"), 50)],
-            },
-            Transaction {
-                paid_by: Person("B"),
-                split_by: vec![(Person("A"), 25), (Person("B"), 25)],
-            },
-            Transaction {
-                paid_by: Person("C"),
-                split_by: vec![(Person("A"), 100), (Person("B"), 150), (Person("C"), 50)],
-            },
-            Transaction {
-                paid_by: Person("D"),
-                split_by: vec![(Person("D"), 10), (Person("E"), 10)],
-            },
-            Transaction {
-                paid_by: Person("A"),
-                split_by: vec![(Person("A"), 5), (Person("E"), 15), (Person("C"), 20)],
-            },
-        ];
-
-        let debts = simplify(transactions);
-        assert_eq!(
-            debts,
-            vec![
-                Debt {
-                    amount: 175,
-                    owing: Person("B"),
-                    owed: Person("C")
-                },
-                Debt {
-                    amount: 40,
-                    owing: Person("A"),
-                    owed: Person("C")
-                },
-                Debt {
-                    amount: 15,
-                    owing: Person("E"),
-                    owed: Person("C")
-                },
-                Debt {
-                    amount: 10,
-                    owing: Person("E"),
-                    owed: Person("D")
-                }
-            ]
-        );
-    }
+    pub currency: Currency,
 }
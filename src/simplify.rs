@@ -0,0 +1,387 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Currency, CurrencyUnit, Debt, Person, SimplifyError, Transaction};
+
+/// Maximum number of non-zero participants `simplify_minimal` will run the
+/// exponential exact DP over before falling back to the greedy heuristic.
+///
+/// The DP visits every (mask, submask) pair, which is `O(3^n)` total work.
+/// At `n = 14` that's ~4.8M iterations (sub-second); each additional
+/// participant roughly triples it, so this is kept well below 20 to avoid
+/// multi-second calls on a request path.
+const MAX_EXACT_PARTICIPANTS: usize = 14;
+
+/// Computes net balances for a non-empty, single-currency list of
+/// transactions, returning the balances alongside that shared currency.
+fn net_balances(
+    transactions: &[Transaction],
+) -> Result<(HashMap<Person, CurrencyUnit>, Currency), SimplifyError> {
+    let currency = transactions[0].currency;
+    let mut balances: HashMap<Person, CurrencyUnit> = HashMap::new();
+
+    for transaction in transactions.iter() {
+        if transaction.currency != currency {
+            return Err(SimplifyError::MixedCurrencies);
+        }
+
+        if transaction.split_by.is_empty() {
+            return Err(SimplifyError::EmptySplit);
+        }
+
+        let mut total: CurrencyUnit = 0;
+        for (person, amount) in transaction.split_by.iter() {
+            if *amount < 0 {
+                return Err(SimplifyError::NegativeAmount {
+                    person: person.clone(),
+                });
+            }
+            total = total
+                .checked_add(*amount)
+                .ok_or(SimplifyError::Overflow)?;
+        }
+
+        if let Some(expected) = transaction.declared_total {
+            if expected != total {
+                return Err(SimplifyError::UnbalancedTransaction {
+                    expected,
+                    actual: total,
+                });
+            }
+        }
+
+        // Credit the person who paid
+        let payer_balance = balances.entry(transaction.paid_by.clone()).or_default();
+        *payer_balance = payer_balance
+            .checked_add(total)
+            .ok_or(SimplifyError::Overflow)?;
+
+        // Debit the people who owe
+        for (person, amount) in transaction.split_by.iter() {
+            let balance = balances.entry(person.clone()).or_default();
+            *balance = balance
+                .checked_sub(*amount)
+                .ok_or(SimplifyError::Overflow)?;
+        }
+    }
+
+    Ok((balances, currency))
+}
+
+/// Settles a set of non-zero balances by repeatedly matching the largest
+/// creditor against the largest debtor. Correct, but not always minimal in
+/// the number of transfers it produces.
+fn settle_greedy(balances: Vec<(Person, CurrencyUnit)>, currency: Currency) -> Vec<Debt> {
+    let mut sorted_balances: BTreeMap<CurrencyUnit, Person> = balances
+        .into_iter()
+        .map(|(person, amount)| (amount, person))
+        .collect();
+
+    let mut debts = vec![];
+
+    // Pop the smallest and largest balances and settle them, adding any remaining balance back to the sorted balances
+    while sorted_balances.len() > 1 {
+        let (min_amount, min_person) = sorted_balances.pop_first().unwrap();
+        let (max_amount, max_person) = sorted_balances.pop_last().unwrap();
+
+        let amount = min_amount.abs().min(max_amount.abs());
+        let debt = Debt {
+            amount,
+            owing: min_person.clone(),
+            owed: max_person.clone(),
+            currency,
+        };
+
+        if min_amount.abs() > max_amount.abs() {
+            sorted_balances.insert(min_amount + max_amount, min_person);
+        } else if max_amount.abs() > min_amount.abs() {
+            sorted_balances.insert(min_amount + max_amount, max_person);
+        }
+
+        debts.push(debt);
+    }
+
+    debts
+}
+
+pub fn simplify(transactions: Vec<Transaction>) -> Result<Vec<Debt>, SimplifyError> {
+    if transactions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (balances, currency) = net_balances(&transactions)?;
+    let balances: Vec<(Person, CurrencyUnit)> = balances
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0)
+        .collect();
+
+    Ok(settle_greedy(balances, currency))
+}
+
+/// Settles the same net balances as `simplify`, but minimizes the number of
+/// settlement transactions instead of just matching largest-creditor to
+/// largest-debtor.
+///
+/// The minimum number of transfers for `n` non-zero participants is
+/// `n - g`, where `g` is the maximum number of disjoint subsets whose
+/// balances each sum to zero (a zero-sum group of size `k` only ever needs
+/// `k - 1` transfers to settle internally). `g` is found with a bitmask DP
+/// over the participants: `sum[mask]` is precomputed for every subset, and
+/// `dp[mask]` is the best partition of `mask` into zero-sum groups, trying
+/// every non-empty zero-sum submask as the next group to peel off. Once the
+/// optimal partition is recovered from the DP choices, each group is
+/// settled independently with the existing greedy pass.
+///
+/// Because the DP is exponential in the number of participants, inputs
+/// larger than `MAX_EXACT_PARTICIPANTS` fall back to the greedy heuristic
+/// so they stay tractable.
+pub fn simplify_minimal(transactions: Vec<Transaction>) -> Result<Vec<Debt>, SimplifyError> {
+    if transactions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (balances, currency) = net_balances(&transactions)?;
+    let people: Vec<(Person, CurrencyUnit)> = balances
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0)
+        .collect();
+
+    let n = people.len();
+    if n > MAX_EXACT_PARTICIPANTS {
+        return Ok(settle_greedy(people, currency));
+    }
+
+    let amounts: Vec<CurrencyUnit> = people.iter().map(|(_, amount)| *amount).collect();
+    let group_count = 1usize << n;
+
+    // sum[mask] is the net balance of the subset of participants in `mask`.
+    let mut sum = vec![0 as CurrencyUnit; group_count];
+    for mask in 1..group_count {
+        let lowest = mask & mask.wrapping_neg();
+        let index = lowest.trailing_zeros() as usize;
+        sum[mask] = sum[mask ^ lowest] + amounts[index];
+    }
+
+    // dp[mask] is the max number of disjoint zero-sum groups within `mask`;
+    // choice[mask] is the submask of the group that achieves it.
+    let mut dp = vec![0u32; group_count];
+    let mut choice = vec![0usize; group_count];
+    for mask in 1..group_count {
+        let mut submask = mask;
+        while submask > 0 {
+            if sum[submask] == 0 {
+                let candidate = 1 + dp[mask ^ submask];
+                if candidate > dp[mask] {
+                    dp[mask] = candidate;
+                    choice[mask] = submask;
+                }
+            }
+            submask = (submask - 1) & mask;
+        }
+    }
+
+    let mut debts = Vec::new();
+    let mut mask = group_count - 1;
+    while mask > 0 {
+        let group = choice[mask];
+        if group == 0 {
+            // The DP only ever leaves a zero-sum remainder (the full set
+            // always sums to zero), but settle it greedily as a fallback.
+            let remaining = people_in(mask, &people);
+            debts.extend(settle_greedy(remaining, currency));
+            break;
+        }
+
+        debts.extend(settle_greedy(people_in(group, &people), currency));
+        mask ^= group;
+    }
+
+    Ok(debts)
+}
+
+fn people_in(mask: usize, people: &[(Person, CurrencyUnit)]) -> Vec<(Person, CurrencyUnit)> {
+    (0..people.len())
+        .filter(|index| mask & (1 << index) != 0)
+        .map(|index| people[index].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CURRENCY: Currency = Currency::new("USD");
+
+    fn transaction(paid_by: Person, split_by: Vec<(Person, CurrencyUnit)>) -> Transaction {
+        Transaction {
+            paid_by,
+            split_by,
+            declared_total: None,
+            currency: TEST_CURRENCY,
+        }
+    }
+
+    #[test]
+    fn test_simplify() {
+        let transactions = vec![
+            transaction(
+                Person::new("A"),
+                vec![(Person::new("A"), 50), (Person::new("B"), 50)],
+            ),
+            transaction(
+                Person::new("B"),
+                vec![(Person::new("A"), 25), (Person::new("B"), 25)],
+            ),
+            transaction(
+                Person::new("C"),
+                vec![
+                    (Person::new("A"), 100),
+                    (Person::new("B"), 150),
+                    (Person::new("C"), 50),
+                ],
+            ),
+            transaction(
+                Person::new("D"),
+                vec![(Person::new("D"), 10), (Person::new("E"), 10)],
+            ),
+            transaction(
+                Person::new("A"),
+                vec![
+                    (Person::new("A"), 5),
+                    (Person::new("E"), 15),
+                    (Person::new("C"), 20),
+                ],
+            ),
+        ];
+
+        let debts = simplify(transactions).unwrap();
+        assert_eq!(
+            debts,
+            vec![
+                Debt {
+                    amount: 175,
+                    owing: Person::new("B"),
+                    owed: Person::new("C"),
+                    currency: TEST_CURRENCY,
+                },
+                Debt {
+                    amount: 40,
+                    owing: Person::new("A"),
+                    owed: Person::new("C"),
+                    currency: TEST_CURRENCY,
+                },
+                Debt {
+                    amount: 15,
+                    owing: Person::new("E"),
+                    owed: Person::new("C"),
+                    currency: TEST_CURRENCY,
+                },
+                Debt {
+                    amount: 10,
+                    owing: Person::new("E"),
+                    owed: Person::new("D"),
+                    currency: TEST_CURRENCY,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify_minimal_settles_independent_zero_sum_groups() {
+        // Two independent zero-sum groups: {A, B} and {C, D, E}. Because the
+        // magnitudes overlap (3 vs 2/1/-3), a purely greedy largest/smallest
+        // match pairs across groups and needs 4 transfers; the minimal
+        // partition needs only (2 - 1) + (3 - 1) = 3.
+        let transactions = vec![
+            transaction(Person::new("A"), vec![(Person::new("B"), 3)]),
+            transaction(Person::new("C"), vec![(Person::new("E"), 2)]),
+            transaction(Person::new("D"), vec![(Person::new("E"), 1)]),
+        ];
+
+        let debts = simplify_minimal(transactions).unwrap();
+        assert_eq!(debts.len(), 3);
+
+        let mut reconstructed: HashMap<Person, CurrencyUnit> = HashMap::new();
+        for debt in &debts {
+            *reconstructed.entry(debt.owed.clone()).or_default() += debt.amount;
+            *reconstructed.entry(debt.owing.clone()).or_default() -= debt.amount;
+        }
+
+        assert_eq!(reconstructed[&Person::new("A")], 3);
+        assert_eq!(reconstructed[&Person::new("B")], -3);
+        assert_eq!(reconstructed[&Person::new("C")], 2);
+        assert_eq!(reconstructed[&Person::new("D")], 1);
+        assert_eq!(reconstructed[&Person::new("E")], -3);
+    }
+
+    #[test]
+    fn test_simplify_rejects_empty_split() {
+        let transactions = vec![transaction(Person::new("A"), vec![])];
+        assert_eq!(simplify(transactions), Err(SimplifyError::EmptySplit));
+    }
+
+    #[test]
+    fn test_simplify_rejects_negative_amount() {
+        let transactions = vec![transaction(
+            Person::new("A"),
+            vec![(Person::new("B"), -10)],
+        )];
+        assert_eq!(
+            simplify(transactions),
+            Err(SimplifyError::NegativeAmount {
+                person: Person::new("B")
+            })
+        );
+    }
+
+    #[test]
+    fn test_simplify_rejects_unbalanced_declared_total() {
+        let transactions = vec![Transaction {
+            paid_by: Person::new("A"),
+            split_by: vec![(Person::new("A"), 50), (Person::new("B"), 50)],
+            declared_total: Some(200),
+            currency: TEST_CURRENCY,
+        }];
+        assert_eq!(
+            simplify(transactions),
+            Err(SimplifyError::UnbalancedTransaction {
+                expected: 200,
+                actual: 100
+            })
+        );
+    }
+
+    /// Builds transactions for `debtor_count` debtors who all owe a
+    /// single sink (so there are `debtor_count + 1` non-zero participants
+    /// total). Each debtor owes a distinct amount, so no subset of them
+    /// sums to zero on its own; the only zero-sum group is the full set,
+    /// making the minimal settlement always `debtor_count` transfers.
+    fn star_transactions(debtor_count: usize) -> Vec<Transaction> {
+        (0..debtor_count)
+            .map(|i| {
+                transaction(
+                    Person::new(format!("debtor-{i}")),
+                    vec![(Person::new("sink"), (i + 1) as CurrencyUnit)],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simplify_minimal_runs_the_exact_dp_at_the_participant_cap() {
+        // debtor_count + 1 sink == MAX_EXACT_PARTICIPANTS, right at the
+        // boundary of the exact DP path, so this exercises the DP's
+        // actual runtime instead of just the fallback.
+        let transactions = star_transactions(MAX_EXACT_PARTICIPANTS - 1);
+        let debts = simplify_minimal(transactions).unwrap();
+        assert_eq!(debts.len(), MAX_EXACT_PARTICIPANTS - 1);
+    }
+
+    #[test]
+    fn test_simplify_minimal_falls_back_to_greedy_above_the_cap() {
+        // debtor_count + 1 sink == MAX_EXACT_PARTICIPANTS + 1, just over
+        // the cap, so this exercises the greedy fallback.
+        let transactions = star_transactions(MAX_EXACT_PARTICIPANTS);
+        let debts = simplify_minimal(transactions).unwrap();
+        assert_eq!(debts.len(), MAX_EXACT_PARTICIPANTS);
+    }
+}
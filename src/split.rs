@@ -0,0 +1,119 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::{CurrencyUnit, Person, SimplifyError};
+
+/// A way of describing how a transaction's total should be divided among
+/// its participants, without requiring the caller to pre-compute exact
+/// per-person minor-unit amounts.
+#[derive(Debug, Clone)]
+pub enum SplitStrategy {
+    /// Divide the total evenly among the given people.
+    Equal(Vec<Person>),
+    /// Divide the total proportionally to each person's share count.
+    Shares(Vec<(Person, u32)>),
+    /// Divide the total proportionally to each person's percentage.
+    /// Percentages need not sum to exactly 100; they're treated as
+    /// relative weights.
+    Percentage(Vec<(Person, Decimal)>),
+    /// Use the given amounts as-is, with no further allocation.
+    Exact(Vec<(Person, CurrencyUnit)>),
+}
+
+impl SplitStrategy {
+    /// Expands this strategy into concrete `split_by` entries that sum
+    /// exactly to `total`.
+    ///
+    /// Shares allocated by weight (`Equal`, `Shares`, `Percentage`) rarely
+    /// divide evenly in integer minor units, so the remainder is
+    /// distributed one unit at a time to the people with the largest
+    /// fractional remainder, ties broken by the order they appear in the
+    /// strategy. This guarantees the result always sums exactly to
+    /// `total`, with no dust silently dropped or gained.
+    ///
+    /// Returns `Err(SimplifyError::ZeroWeightTotal)` if a weighted
+    /// strategy's weights are all zero (e.g. an empty `Equal` or a
+    /// `Shares`/`Percentage` list that sums to zero), since there's no
+    /// proportion to allocate `total` by.
+    pub fn expand(&self, total: CurrencyUnit) -> Result<Vec<(Person, CurrencyUnit)>, SimplifyError> {
+        match self {
+            SplitStrategy::Equal(people) => {
+                let weights = people.iter().map(|p| (p.clone(), Decimal::ONE)).collect();
+                allocate_by_weight(total, weights)
+            }
+            SplitStrategy::Shares(shares) => {
+                let weights = shares
+                    .iter()
+                    .map(|(p, count)| (p.clone(), Decimal::from(*count)))
+                    .collect();
+                allocate_by_weight(total, weights)
+            }
+            SplitStrategy::Percentage(percentages) => {
+                let weights = percentages
+                    .iter()
+                    .map(|(p, pct)| (p.clone(), *pct))
+                    .collect();
+                allocate_by_weight(total, weights)
+            }
+            SplitStrategy::Exact(amounts) => Ok(amounts.clone()),
+        }
+    }
+}
+
+/// Distributes `total` across `weights` proportionally, using the largest
+/// remainder method so the allocated amounts always sum exactly to
+/// `total`. Each entry's floor is taken first, then the leftover units are
+/// handed out one at a time to the entries with the largest fractional
+/// remainder (ties broken by their original, stable order).
+///
+/// Rejects a zero total weight rather than silently handing out a
+/// zero-amount split for every person, which would make `total` vanish
+/// with no error.
+pub(crate) fn allocate_by_weight(
+    total: CurrencyUnit,
+    weights: Vec<(Person, Decimal)>,
+) -> Result<Vec<(Person, CurrencyUnit)>, SimplifyError> {
+    let weight_total: Decimal = weights.iter().map(|(_, w)| w).sum();
+    if weight_total.is_zero() {
+        return Err(SimplifyError::ZeroWeightTotal);
+    }
+
+    let total = Decimal::from(total);
+    let mut allocations: Vec<(Person, CurrencyUnit, Decimal)> = weights
+        .into_iter()
+        .map(|(person, weight)| {
+            let raw = total * weight / weight_total;
+            let floor = raw.floor();
+            (person, floor.to_i64().unwrap_or(0), raw - floor)
+        })
+        .collect();
+
+    let allocated: CurrencyUnit = allocations.iter().map(|(_, amount, _)| amount).sum();
+    let remainder = (total.to_i64().unwrap_or(0) - allocated).max(0);
+
+    let mut order: Vec<usize> = (0..allocations.len()).collect();
+    order.sort_by(|&a, &b| allocations[b].2.cmp(&allocations[a].2));
+
+    for &index in order.iter().take(remainder as usize) {
+        allocations[index].1 += 1;
+    }
+
+    Ok(allocations
+        .into_iter()
+        .map(|(person, amount, _)| (person, amount))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_rejects_zero_weight_total() {
+        let strategy = SplitStrategy::Shares(vec![(Person::new("A"), 0), (Person::new("B"), 0)]);
+        assert_eq!(strategy.expand(100), Err(SimplifyError::ZeroWeightTotal));
+
+        let strategy = SplitStrategy::Equal(vec![]);
+        assert_eq!(strategy.expand(100), Err(SimplifyError::ZeroWeightTotal));
+    }
+}